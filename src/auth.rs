@@ -0,0 +1,123 @@
+use std::future::{ready, Ready};
+use std::rc::Rc;
+use std::sync::{Arc, RwLock};
+
+use actix_web::body::EitherBody;
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::{Error, HttpResponse};
+use futures_util::future::LocalBoxFuture;
+
+use crate::cfg::Config;
+
+/// Rejects requests missing a valid token when `Config::auth_token` is set,
+/// on every route — the embedded webview always talks to
+/// `http://localhost:{port}` regardless of `bind_host`, so requests from a
+/// loopback peer are exempted instead of carving out specific paths;
+/// anyone connecting from off-box must present the token.
+///
+/// The token can be presented either as an `Authorization: Bearer <token>`
+/// header or as a `?token=<token>` query parameter. The query parameter
+/// exists because this also gates `/` and `/ws`: a plain browser page load
+/// or WebSocket handshake can't attach a custom header, so a field tablet
+/// on the LAN has no way to reach the dashboard at all without it.
+pub struct BearerAuth {
+    cfg: Arc<RwLock<Config>>,
+}
+
+impl BearerAuth {
+    pub fn new(cfg: Arc<RwLock<Config>>) -> Self {
+        BearerAuth { cfg }
+    }
+}
+
+/// Pulls the bearer token out of either the `Authorization` header or a
+/// `?token=` query parameter, preferring the header when both are present.
+fn token_from_request(req: &ServiceRequest) -> Option<String> {
+    if let Some(header) = req
+        .headers()
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    {
+        return Some(header.to_string());
+    }
+
+    req.query_string()
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+        .map(|token| token.to_string())
+}
+
+/// Compares two strings in time proportional to their length instead of
+/// returning as soon as a byte differs, so a timing side channel can't be
+/// used to guess the configured token one byte at a time.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+impl<S, B> Transform<S, ServiceRequest> for BearerAuth
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = BearerAuthMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(BearerAuthMiddleware {
+            service: Rc::new(service),
+            cfg: self.cfg.clone(),
+        }))
+    }
+}
+
+pub struct BearerAuthMiddleware<S> {
+    service: Rc<S>,
+    cfg: Arc<RwLock<Config>>,
+}
+
+impl<S, B> Service<ServiceRequest> for BearerAuthMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let from_loopback = req
+            .peer_addr()
+            .map(|addr| addr.ip().is_loopback())
+            .unwrap_or(false);
+
+        let expected = self.cfg.read().unwrap().auth_token.clone();
+
+        let authorized = from_loopback
+            || match &expected {
+                None => false,
+                Some(expected) => token_from_request(&req)
+                    .map(|token| constant_time_eq(&token, expected))
+                    .unwrap_or(false),
+            };
+
+        if !authorized {
+            let (req, _) = req.into_parts();
+            let response = HttpResponse::Unauthorized().finish().map_into_right_body();
+            return Box::pin(async move { Ok(ServiceResponse::new(req, response)) });
+        }
+
+        let fut = self.service.call(req);
+        Box::pin(async move { fut.await.map(ServiceResponse::map_into_left_body) })
+    }
+}
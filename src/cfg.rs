@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+
+use crate::keys::{KeyBindings, ACTIONS};
+
+/// Highest team number `Profile::validate` accepts. FRC team numbers are
+/// issued sequentially and, as of this writing, stay well under this; 0 is
+/// a separate sentinel meaning "not configured".
+const MAX_TEAM_NUMBER: u32 = 99_999;
+/// Highest joystick axis/button index `Profile::validate` accepts. No real
+/// HID reports more slots than this, so anything higher is bogus.
+const MAX_JOYSTICK_INDEX: u32 = 63;
+
+/// Persisted, operator-editable configuration. Loaded via `confy` at
+/// startup and written back on a clean exit.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Config {
+    pub team_number: u32,
+    pub keybindings: KeyBindings,
+    pub joystick_mapping: Vec<u32>,
+    /// Webhook URLs notified of state transitions like brownouts or comms
+    /// loss. Empty by default, so notifications are an explicit opt-in.
+    pub webhooks: Vec<String>,
+    /// Pack voltage below which the robot is considered to be browning
+    /// out, for the `battery_low` webhook notification.
+    #[serde(default = "default_low_voltage_threshold")]
+    pub low_voltage_threshold: f32,
+    /// Host the embedded webserver binds to. Defaults to loopback-only;
+    /// set to e.g. "0.0.0.0" to allow a field tablet on the team LAN to
+    /// reach the dashboard.
+    #[serde(default = "default_bind_host")]
+    pub bind_host: String,
+    /// When set, every route on the embedded webserver (the dashboard,
+    /// its websocket, the stdout console, and the `/api/*` endpoints)
+    /// requires a matching token, carried as either an
+    /// `Authorization: Bearer <token>` header or a `?token=<token>` query
+    /// parameter, except for requests from loopback, which are always
+    /// exempt. `enforce_auth_invariant` forces `bind_host` back to loopback
+    /// if this is unset, so LAN exposure always requires a token.
+    #[serde(default)]
+    pub auth_token: Option<String>,
+}
+
+fn default_bind_host() -> String {
+    "127.0.0.1".into()
+}
+
+fn default_low_voltage_threshold() -> f32 {
+    7.0
+}
+
+const LOOPBACK_HOSTS: &[&str] = &["127.0.0.1", "localhost", "::1"];
+
+impl Config {
+    /// Refuses to bind off loopback without an auth token: if `bind_host` is
+    /// non-loopback and `auth_token` is unset, forces `bind_host` back to
+    /// loopback and logs a warning. Returns `true` if it had to do so.
+    pub fn enforce_auth_invariant(&mut self) -> bool {
+        if self.auth_token.is_none() && !LOOPBACK_HOSTS.contains(&self.bind_host.as_str()) {
+            log::warn!(
+                "bind_host \"{}\" is set with no auth_token configured; refusing to expose the \
+                 webserver off loopback until one is set",
+                self.bind_host,
+            );
+            self.bind_host = default_bind_host();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            team_number: 0,
+            keybindings: KeyBindings::default(),
+            joystick_mapping: Vec::new(),
+            webhooks: Vec::new(),
+            low_voltage_threshold: default_low_voltage_threshold(),
+            bind_host: default_bind_host(),
+            auth_token: None,
+        }
+    }
+}
+
+/// The subset of `Config` that's safe to share between operators or back up
+/// independently of the machine it came from: team number, keybindings, and
+/// joystick mapping, but not machine-local network settings.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Profile {
+    pub team_number: u32,
+    pub keybindings: KeyBindings,
+    pub joystick_mapping: Vec<u32>,
+}
+
+impl Profile {
+    pub fn from_config(cfg: &Config) -> Self {
+        Profile {
+            team_number: cfg.team_number,
+            keybindings: cfg.keybindings.clone(),
+            joystick_mapping: cfg.joystick_mapping.clone(),
+        }
+    }
+
+    pub fn apply_to(&self, cfg: &mut Config) {
+        cfg.team_number = self.team_number;
+        cfg.keybindings = self.keybindings.clone();
+        cfg.joystick_mapping = self.joystick_mapping.clone();
+    }
+
+    /// Rejects a profile carrying values the rest of the app can't
+    /// sensibly act on, beyond just deserializing successfully: an
+    /// out-of-range team number, a keybinding naming an action `bind_keys`
+    /// doesn't recognize, or a joystick mapping entry outside the range of
+    /// indices a real joystick could report.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.team_number > MAX_TEAM_NUMBER {
+            return Err(format!(
+                "team_number {} is outside the valid range (0-{})",
+                self.team_number, MAX_TEAM_NUMBER
+            ));
+        }
+
+        for action in self.keybindings.0.keys() {
+            if !ACTIONS.contains(&action.as_str()) {
+                return Err(format!("unrecognized keybinding action \"{}\"", action));
+            }
+        }
+
+        if let Some(bad) = self.joystick_mapping.iter().find(|&&v| v > MAX_JOYSTICK_INDEX) {
+            return Err(format!(
+                "joystick mapping value {} is outside the valid index range (0-{})",
+                bad, MAX_JOYSTICK_INDEX
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use actix::prelude::*;
+use actix_web_actors::ws;
+use serde::{Deserialize, Serialize};
+
+use crate::state::State;
+use crate::webserver::SharedMainAddr;
+use std::sync::{Arc, RwLock};
+
+/// How often the server pings the webview client to check liveness.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+/// How long we'll wait without a pong before assuming the client is gone.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Messages sent from the backend to a connected IPC client, via `do_send`
+/// on the session's `Addr`, and written to the socket as JSON text frames.
+/// Text frames received from the client are not parsed into `Message`s;
+/// they only count as client traffic for the heartbeat.
+#[derive(Message, Serialize, Deserialize, Clone, Debug)]
+#[rtype(result = "()")]
+#[serde(tag = "type")]
+pub enum Message {
+    RobotStateUpdate {
+        comms_alive: bool,
+        code_alive: bool,
+        simulator: bool,
+        joysticks: bool,
+        voltage: f32,
+    },
+    UpdateTeamNumber {
+        team_number: u32,
+        from_backend: bool,
+    },
+    Capabilities {
+        backend_keybinds: bool,
+    },
+    KeybindingsUpdated {
+        keybindings: HashMap<String, String>,
+    },
+    JoystickMappingUpdated {
+        joystick_mapping: Vec<u32>,
+    },
+}
+
+/// Which window an `IpcSession` is serving. Only the driver dashboard's
+/// socket dying is a driver-station failure worth disabling the robot
+/// over; the stdout console is just a log viewer.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Role {
+    Main,
+    Console,
+}
+
+/// The websocket session backing a single webview connection (the main
+/// dashboard or the stdout console). Tracks the last time the client was
+/// heard from so a frozen or crashed dashboard can be detected and the
+/// robot disabled rather than left enabled with no one watching it.
+pub struct IpcSession {
+    hb: Instant,
+    state: Arc<RwLock<State>>,
+    role: Role,
+    /// Only set for `Role::Main`: lets a heartbeat timeout clear the shared
+    /// main-session address, so callers elsewhere don't keep observing a
+    /// dead `Addr` and restarting work against it forever.
+    main_addr: Option<SharedMainAddr>,
+}
+
+impl IpcSession {
+    pub fn new(state: Arc<RwLock<State>>, role: Role) -> Self {
+        IpcSession {
+            hb: Instant::now(),
+            state,
+            role,
+            main_addr: None,
+        }
+    }
+
+    /// Like `new`, but for the `Role::Main` session: keeps a handle to the
+    /// shared main-session address so it can be cleared on a heartbeat
+    /// timeout instead of left pointing at a dead actor.
+    pub fn new_main(state: Arc<RwLock<State>>, main_addr: SharedMainAddr) -> Self {
+        IpcSession {
+            hb: Instant::now(),
+            state,
+            role: Role::Main,
+            main_addr: Some(main_addr),
+        }
+    }
+
+    /// Pings the client on `HEARTBEAT_INTERVAL` and checks that a pong (or
+    /// any other client traffic) has been seen within `CLIENT_TIMEOUT`. If
+    /// not, and this is the main dashboard session, the dashboard is assumed
+    /// dead: disable the robot and drop the connection instead of continuing
+    /// to stream stale state. A dead stdout console has no bearing on robot
+    /// safety, so it's just dropped.
+    fn heartbeat(&self, ctx: &mut ws::WebsocketContext<Self>) {
+        ctx.run_interval(HEARTBEAT_INTERVAL, |act, ctx| {
+            if Instant::now().duration_since(act.hb) > CLIENT_TIMEOUT {
+                if act.role == Role::Main {
+                    log::warn!("IPC client heartbeat timed out, disabling robot and closing socket");
+
+                    {
+                        let mut state = act.state.write().unwrap();
+                        state.ds.disable();
+                        state.comms_lost = true;
+                    }
+
+                    // Only clear the shared address if it's still pointing
+                    // at this session — a faster reconnect may already have
+                    // replaced it with a live one before this timeout fired.
+                    if let Some(main_addr) = &act.main_addr {
+                        let mut current = main_addr.write().unwrap();
+                        if current.as_ref() == Some(&ctx.address()) {
+                            *current = None;
+                        }
+                    }
+                } else {
+                    log::warn!("Stdout console heartbeat timed out, closing socket");
+                }
+
+                ctx.stop();
+                return;
+            }
+
+            ctx.ping(b"");
+        });
+    }
+}
+
+impl Actor for IpcSession {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        self.heartbeat(ctx);
+    }
+}
+
+impl Handler<Message> for IpcSession {
+    type Result = ();
+
+    fn handle(&mut self, msg: Message, ctx: &mut Self::Context) {
+        if let Ok(text) = serde_json::to_string(&msg) {
+            ctx.text(text);
+        }
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for IpcSession {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        match msg {
+            Ok(ws::Message::Ping(msg)) => {
+                self.hb = Instant::now();
+                ctx.pong(&msg);
+            }
+            Ok(ws::Message::Pong(_)) => {
+                self.hb = Instant::now();
+            }
+            Ok(ws::Message::Text(_)) => {
+                self.hb = Instant::now();
+            }
+            Ok(ws::Message::Binary(_)) => {
+                self.hb = Instant::now();
+            }
+            Ok(ws::Message::Close(reason)) => {
+                ctx.close(reason);
+                ctx.stop();
+            }
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::Duration;
+
+use actix::Addr;
+use serde::{Deserialize, Serialize};
+
+use crate::ipc::IpcSession;
+use crate::state::State;
+
+/// The only action names `bind_keys` dispatches on; a keybinding naming
+/// anything else can never fire and is almost certainly a typo or a
+/// forged upload.
+pub const ACTIONS: &[&str] = &["enable", "disable", "estop"];
+
+/// The operator-configurable keyboard shortcuts, keyed by action name (e.g.
+/// `"enable"`, `"disable"`, `"estop"`) and mapped to the key that triggers
+/// them. Stored in `Config` and round-tripped through profile import/export.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct KeyBindings(pub HashMap<String, String>);
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let mut bindings = HashMap::new();
+        bindings.insert("enable".into(), "Return".into());
+        bindings.insert("disable".into(), "Space".into());
+        bindings.insert("estop".into(), "Escape".into());
+        KeyBindings(bindings)
+    }
+}
+
+/// The live, runtime-mutable keybindings. Seeded from `Config::keybindings`
+/// at startup and updated in place by a profile import, so the polling loop
+/// below picks up the change on its very next tick with no restart needed.
+pub type SharedKeyBindings = Arc<RwLock<KeyBindings>>;
+
+/// How often the backend polls the keyboard for bound keys going down.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Registers global keyboard shortcuts for enable/disable/e-stop where the
+/// host platform supports backend-level key capture, by polling for the
+/// currently-bound keys and driving `state.ds` directly. `keybindings` is
+/// re-read on every tick rather than snapshotted once, so an imported
+/// profile's keybindings take effect immediately. Returns whether
+/// keybindings were actually wired up, which the frontend uses to decide
+/// whether it needs to handle them itself instead.
+pub fn bind_keys(
+    state: Arc<RwLock<State>>,
+    _addr: Addr<IpcSession>,
+    keybindings: SharedKeyBindings,
+) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        let _ = (state, keybindings);
+        false
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        thread::spawn(move || {
+            let device = device_query::DeviceState::new();
+            let mut held: HashMap<String, bool> = HashMap::new();
+
+            loop {
+                let pressed = device_query::DeviceQuery::get_keys(&device);
+                let bindings = keybindings.read().unwrap().0.clone();
+
+                for (action, key) in &bindings {
+                    let is_down = pressed.iter().any(|k| &format!("{:?}", k) == key);
+                    let was_down = *held.get(action).unwrap_or(&false);
+
+                    if is_down && !was_down {
+                        let mut state = state.write().unwrap();
+                        match action.as_str() {
+                            "enable" => state.ds.enable(),
+                            "disable" => state.ds.disable(),
+                            "estop" => state.ds.estop(),
+                            _ => {}
+                        }
+                    }
+
+                    held.insert(action.clone(), is_down);
+                }
+
+                thread::sleep(POLL_INTERVAL);
+            }
+        });
+
+        true
+    }
+}
@@ -2,6 +2,9 @@ use std::sync::{mpsc, Arc, RwLock};
 use std::thread;
 use std::time::Duration;
 
+use actix::Addr;
+use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
+use signal_hook::iterator::Signals;
 use wry::application::dpi::LogicalSize;
 use wry::application::event::{Event, WindowEvent};
 use wry::application::event_loop::{ControlFlow, EventLoop};
@@ -9,10 +12,12 @@ use wry::application::window::WindowBuilder;
 use wry::webview::WebViewBuilder;
 
 // Your module declarations
+mod auth;
 mod cfg;
 mod input;
 mod ipc;
 mod keys;
+mod notify;
 mod panic;
 mod resources;
 mod scrn;
@@ -25,14 +30,21 @@ use crate::state::State;
 use cfg::Config;
 use ds::DsMode;
 use ipc::*;
+use keys::SharedKeyBindings;
 use webserver::SetAddr;
 
 const PERCENT_WIDTH: f64 = 0.7906295754026355;
 const PERCENT_HEIGHT: f64 = 0.42;
 
+/// Out-of-band events delivered to the wry event loop from outside the
+/// windowing thread, currently just a request to shut down gracefully.
+enum UserEvent {
+    Shutdown,
+}
+
 fn main() -> wry::Result<()> {
     env_logger::init();
-    let mut cfg = confy::load::<Config>("conductor").unwrap();
+    let cfg = Arc::new(RwLock::new(confy::load::<Config>("conductor").unwrap()));
 
     if std::env::var("RUST_BACKTRACE").is_err() {
         std::panic::set_hook(Box::new(panic::hook));
@@ -55,14 +67,17 @@ fn main() -> wry::Result<()> {
     let (tx, rx) = mpsc::channel();
     let (stdout_tx, stdout_rx) = mpsc::channel();
 
-    let port = webserver::launch_webserver(state.clone(), tx, stdout_tx);
+    let keybindings: SharedKeyBindings = Arc::new(RwLock::new(cfg.read().unwrap().keybindings.clone()));
+
+    let (port, main_addr) =
+        webserver::launch_webserver(state.clone(), cfg.clone(), keybindings.clone(), tx, stdout_tx);
     println!("Webserver launched on port {}", port);
 
     let (width, height) = scrn::screen_resolution();
     println!("Detected Resolution {} {}", width, height);
 
     // Create the event loop
-    let event_loop = EventLoop::new();
+    let event_loop = EventLoop::<UserEvent>::with_user_event();
 
     // Create the main window
     let main_window = WindowBuilder::new()
@@ -105,27 +120,60 @@ fn main() -> wry::Result<()> {
 
     state.write().unwrap().wire_stdout(addr.clone());
 
-    if cfg.team_number != 0 {
+    let team_number = cfg.read().unwrap().team_number;
+    if team_number != 0 {
         addr.do_send(Message::UpdateTeamNumber {
-            team_number: cfg.team_number,
+            team_number,
             from_backend: true,
         });
-        state.write().unwrap().update_ds(cfg.team_number);
+        state.write().unwrap().update_ds(team_number);
     }
 
     // Bind key events
-    let keybindings_enabled = keys::bind_keys(state.clone(), addr.clone());
+    let keybindings_enabled = keys::bind_keys(state.clone(), addr.clone(), keybindings.clone());
     addr.do_send(Message::Capabilities {
         backend_keybinds: keybindings_enabled,
     });
 
-    // Start the input thread
-    input::input_thread(addr.clone());
+    // input::input_thread takes a concrete session address rather than the
+    // shared, reconnect-aware one, so watch for a live main session and
+    // (re)start it whenever the session it's currently using has
+    // disconnected. IpcSession clears main_addr back to None on its own
+    // heartbeat timeout, so a vanished dashboard reads as `None` here
+    // rather than the same dead `Addr` forever; comparing by identity on
+    // top of that means even a lingering stale entry can't spawn a second
+    // thread for the same session. Together this covers the initial
+    // connection and a dashboard reconnecting after a heartbeat timeout,
+    // without having to touch input.rs itself.
+    {
+        let main_addr = main_addr.clone();
+        thread::spawn(move || {
+            let mut running: Option<Addr<IpcSession>> = None;
+            loop {
+                if running.as_ref().map_or(true, |addr| !addr.connected()) {
+                    let live = main_addr.read().unwrap().clone();
+                    if live.is_some() && live != running {
+                        let addr = live.unwrap();
+                        input::input_thread(addr.clone());
+                        running = Some(addr);
+                    }
+                }
+                thread::sleep(Duration::from_millis(50));
+            }
+        });
+    }
+
+    // Watch for state transitions (brownouts, comms loss, code crashes) and
+    // notify any configured webhooks.
+    notify::watch(state.clone(), cfg.clone());
 
-    // Spawn a thread to send periodic robot state updates
+    // Spawn a thread to send periodic robot state updates. It looks up the
+    // current main-session address on every tick instead of holding onto
+    // the one from startup, so it keeps working across a dashboard
+    // reconnect instead of shouting into a dead actor forever.
     {
         let state = state.clone();
-        let addr = addr.clone();
+        let main_addr = main_addr.clone();
         thread::spawn(move || loop {
             let msg = {
                 let state = state.read().unwrap();
@@ -150,11 +198,35 @@ fn main() -> wry::Result<()> {
                 }
             };
 
-            addr.do_send(msg);
+            if let Some(addr) = main_addr.read().unwrap().clone() {
+                addr.do_send(msg);
+            }
             thread::sleep(Duration::from_millis(50));
         });
     }
 
+    // Listen for SIGINT/SIGTERM/SIGHUP so a terminal Ctrl-C or a process
+    // manager stopping us doesn't leave the robot enabled with an unsaved
+    // config. The wry event loop owns this thread, so the signal thread
+    // can't touch it directly; instead it asks the loop to exit via a
+    // proxied user event.
+    let event_loop_proxy = event_loop.create_proxy();
+    {
+        let sig_state = end_state.clone();
+        let sig_cfg = cfg.clone();
+        let mut signals = Signals::new(&[SIGINT, SIGTERM, SIGHUP]).expect("failed to register signal handlers");
+        thread::spawn(move || {
+            if signals.forever().next().is_some() {
+                log::info!("Received shutdown signal, disabling robot and saving config");
+                save_and_disable(&sig_state, &sig_cfg);
+                let _ = event_loop_proxy.send_event(UserEvent::Shutdown);
+            }
+        });
+    }
+
+    let close_state = end_state.clone();
+    let close_cfg = cfg.clone();
+
     // Run the event loop, moving the webview into the closure to keep it alive
     event_loop.run(move |event, _, control_flow| {
         *control_flow = ControlFlow::Wait;
@@ -171,6 +243,7 @@ fn main() -> wry::Result<()> {
             } => {
                 // Exit the application when the main window is closed
                 if window_id == webview.window().id() {
+                    save_and_disable(&close_state, &close_cfg);
                     *control_flow = ControlFlow::Exit;
                 }
 
@@ -182,14 +255,30 @@ fn main() -> wry::Result<()> {
             Event::MainEventsCleared => {
                 // Perform periodic tasks here if necessary
             }
+            Event::UserEvent(UserEvent::Shutdown) => {
+                *control_flow = ControlFlow::Exit;
+            }
             _ => {}
         }
     });
 
-    // Update and store the team number before exiting
-    cfg.team_number = end_state.read().unwrap().ds.team_number();
-    log::info!("Updating team number to {}", cfg.team_number);
-    confy::store("conductor", cfg).unwrap();
+    // Update and store the team number before exiting. On most platforms
+    // `event_loop.run` never actually returns here, which is why the
+    // main-window close branch and the signal handler above do the same save
+    // themselves rather than relying on this.
+    save_and_disable(&end_state, &cfg);
 
     Ok(())
 }
+
+/// Disables the robot and persists the team number to disk. Called both from
+/// the window-close path and the signal-handling thread so a clean shutdown
+/// happens no matter how the process is asked to exit.
+fn save_and_disable(state: &Arc<RwLock<State>>, cfg: &Arc<RwLock<Config>>) {
+    state.write().unwrap().ds.disable();
+
+    let mut cfg = cfg.write().unwrap();
+    cfg.team_number = state.read().unwrap().ds.team_number();
+    log::info!("Updating team number to {}", cfg.team_number);
+    confy::store("conductor", &*cfg).unwrap();
+}
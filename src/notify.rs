@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::sync::{mpsc, Arc, RwLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::cfg::Config;
+use crate::state::State;
+
+/// How often to re-sample state for transitions, matching the periodic
+/// `RobotStateUpdate` thread in `main`.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Minimum time between webhook deliveries for the same event, so a
+/// flapping signal (e.g. comms dropping in and out) doesn't spam endpoints.
+const DEBOUNCE: Duration = Duration::from_secs(3);
+
+#[derive(Serialize, Clone, Debug)]
+struct WebhookEvent {
+    event: &'static str,
+    timestamp: u64,
+    voltage: f32,
+    team_number: u32,
+}
+
+/// Watches the same state the periodic update thread already computes and
+/// fires a webhook POST for each transition a drive team is likely to miss
+/// as a brief blip in the UI: a brownout, comms dropping, or robot code
+/// crashing. Detection and delivery run on separate threads, fed by a
+/// channel, so webhook HTTP latency never blocks the 50 ms state loop.
+pub fn watch(state: Arc<RwLock<State>>, cfg: Arc<RwLock<Config>>) {
+    let (tx, rx) = mpsc::channel::<WebhookEvent>();
+    let deliver_cfg = cfg.clone();
+
+    thread::spawn(move || deliver(rx, deliver_cfg));
+    thread::spawn(move || detect(state, cfg, tx));
+}
+
+fn detect(state: Arc<RwLock<State>>, cfg: Arc<RwLock<Config>>, tx: mpsc::Sender<WebhookEvent>) {
+    let mut low_voltage = false;
+    // `None` until the first real sample comes in, so startup (where comms
+    // and code are almost always not yet up) is treated as a baseline
+    // rather than a `comms_lost`/`code_crashed` transition.
+    let mut comms_alive: Option<bool> = None;
+    let mut code_alive: Option<bool> = None;
+
+    loop {
+        let (voltage, comms, code, team_number) = {
+            let state = state.read().unwrap();
+            let ds = &state.ds;
+            (
+                ds.battery_voltage(),
+                ds.trace().is_connected(),
+                ds.trace().is_code_started(),
+                ds.team_number(),
+            )
+        };
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let mut emit = |event: &'static str| {
+            let _ = tx.send(WebhookEvent {
+                event,
+                timestamp,
+                voltage,
+                team_number,
+            });
+        };
+
+        let threshold = cfg.read().unwrap().low_voltage_threshold;
+        let now_low = voltage > 0.0 && voltage < threshold;
+        if now_low && !low_voltage {
+            emit("battery_low");
+        }
+        low_voltage = now_low;
+
+        if comms_alive == Some(true) && !comms {
+            emit("comms_lost");
+        }
+        comms_alive = Some(comms);
+
+        if code_alive == Some(true) && !code {
+            emit("code_crashed");
+        }
+        code_alive = Some(code);
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn deliver(rx: mpsc::Receiver<WebhookEvent>, cfg: Arc<RwLock<Config>>) {
+    let mut last_sent: HashMap<&'static str, Instant> = HashMap::new();
+
+    for event in rx {
+        let now = Instant::now();
+        if let Some(last) = last_sent.get(event.event) {
+            if now.duration_since(*last) < DEBOUNCE {
+                continue;
+            }
+        }
+        last_sent.insert(event.event, now);
+
+        let urls = cfg.read().unwrap().webhooks.clone();
+        for url in urls {
+            if let Err(err) = ureq::post(&url).send_json(event.clone()) {
+                log::warn!("Failed to deliver {} webhook to {}: {}", event.event, url, err);
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use std::borrow::Cow;
+use std::sync::{mpsc, Arc, RwLock};
+use std::thread;
+
+use actix::prelude::*;
+use actix_multipart::Multipart;
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Responder};
+use actix_web_actors::ws;
+use futures_util::StreamExt;
+use serde::Serialize;
+
+use ds::DsMode;
+
+use crate::auth::BearerAuth;
+use crate::cfg::{Config, Profile};
+use crate::input;
+use crate::ipc::{IpcSession, Message as IpcMessage, Role};
+use crate::keys::SharedKeyBindings;
+use crate::resources::Resources;
+use crate::state::State;
+
+/// Sent to the main IPC session once the stdout console's own session has
+/// been established, so the main window can forward console output to it.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct SetAddr {
+    pub addr: Addr<IpcSession>,
+}
+
+/// The main webview's IPC session address. Updated every time a new main
+/// session connects (including a reconnect after a heartbeat timeout), so
+/// anything sending to it — including callers outside this module — always
+/// reaches whichever session is actually live.
+pub type SharedMainAddr = Arc<RwLock<Option<Addr<IpcSession>>>>;
+
+/// A point-in-time view of driver station state, polled by pit dashboards,
+/// scouting apps, or OBS overlays that don't need the full webview.
+#[derive(Serialize)]
+struct TelemetrySnapshot {
+    team_number: u32,
+    comms_alive: bool,
+    code_alive: bool,
+    simulator: bool,
+    voltage: f32,
+    joysticks: bool,
+}
+
+async fn telemetry(state: web::Data<Arc<RwLock<State>>>) -> impl Responder {
+    let state = state.read().unwrap();
+    let ds = &state.ds;
+
+    let snapshot = TelemetrySnapshot {
+        team_number: ds.team_number(),
+        comms_alive: ds.trace().is_connected(),
+        code_alive: ds.trace().is_code_started(),
+        simulator: ds.ds_mode() == DsMode::Simulation,
+        voltage: ds.battery_voltage(),
+        joysticks: input::JS_STATE.get().unwrap().read().unwrap().has_joysticks(),
+    };
+
+    HttpResponse::Ok().json(snapshot)
+}
+
+async fn assets(path: web::Path<String>) -> impl Responder {
+    let path = path.into_inner();
+    match Resources::get(&path) {
+        Some(content) => {
+            let body: Cow<'static, [u8]> = content;
+            HttpResponse::Ok()
+                .content_type(mime_guess::from_path(&path).first_or_octet_stream().as_ref())
+                .body(body.into_owned())
+        }
+        None => HttpResponse::NotFound().body("404 Not Found"),
+    }
+}
+
+async fn index(_req: HttpRequest) -> impl Responder {
+    let contents = Resources::get("index.html").unwrap();
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(contents.into_owned())
+}
+
+async fn stdout(_req: HttpRequest) -> impl Responder {
+    let contents = Resources::get("stdout.html").unwrap();
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(contents.into_owned())
+}
+
+async fn ws_main(
+    req: HttpRequest,
+    stream: web::Payload,
+    state: web::Data<Arc<RwLock<State>>>,
+    tx: web::Data<mpsc::Sender<Addr<IpcSession>>>,
+    main_addr: web::Data<SharedMainAddr>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let session = IpcSession::new_main(state.get_ref().clone(), main_addr.get_ref().clone());
+    let (addr, resp) = ws::start_with_addr(session, &req, stream)?;
+    *main_addr.write().unwrap() = Some(addr.clone());
+    let _ = tx.send(addr);
+    Ok(resp)
+}
+
+/// Accepts a single-part `multipart/form-data` upload containing a JSON
+/// profile, validates it (structurally, via deserialization, and
+/// semantically, via `Profile::validate`), applies it to the live config,
+/// keybindings, and joystick mapping, and pushes the changes out to the
+/// connected dashboard so it stays in sync without a restart.
+async fn import_profile(
+    mut payload: Multipart,
+    cfg: web::Data<Arc<RwLock<Config>>>,
+    state: web::Data<Arc<RwLock<State>>>,
+    keybindings: web::Data<SharedKeyBindings>,
+    main_addr: web::Data<SharedMainAddr>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let mut applied = false;
+
+    while let Some(field) = payload.next().await {
+        let mut field = field?;
+        let mut bytes = web::BytesMut::new();
+
+        while let Some(chunk) = field.next().await {
+            bytes.extend_from_slice(&chunk?);
+        }
+
+        let profile: Profile = match serde_json::from_slice(&bytes) {
+            Ok(profile) => profile,
+            Err(_) => return Ok(HttpResponse::BadRequest().body("invalid profile")),
+        };
+
+        if let Err(reason) = profile.validate() {
+            return Ok(HttpResponse::BadRequest().body(reason));
+        }
+
+        profile.apply_to(&mut cfg.write().unwrap());
+        *keybindings.write().unwrap() = profile.keybindings.clone();
+
+        {
+            let mut state = state.write().unwrap();
+            state.update_ds(profile.team_number);
+            state.update_joystick_mapping(profile.joystick_mapping.clone());
+        }
+
+        if let Some(addr) = main_addr.read().unwrap().as_ref() {
+            addr.do_send(IpcMessage::UpdateTeamNumber {
+                team_number: profile.team_number,
+                from_backend: true,
+            });
+            addr.do_send(IpcMessage::KeybindingsUpdated {
+                keybindings: profile.keybindings.0.clone(),
+            });
+            addr.do_send(IpcMessage::JoystickMappingUpdated {
+                joystick_mapping: profile.joystick_mapping.clone(),
+            });
+        }
+
+        applied = true;
+    }
+
+    if !applied {
+        return Ok(HttpResponse::BadRequest().body("no profile part in upload"));
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Streams the current team number, keybindings, and joystick mapping back
+/// as a downloadable JSON profile file.
+async fn export_profile(cfg: web::Data<Arc<RwLock<Config>>>) -> impl Responder {
+    let profile = Profile::from_config(&cfg.read().unwrap());
+
+    HttpResponse::Ok()
+        .content_type("application/json")
+        .insert_header((
+            "Content-Disposition",
+            "attachment; filename=\"conductor-profile.json\"",
+        ))
+        .json(profile)
+}
+
+async fn ws_stdout(
+    req: HttpRequest,
+    stream: web::Payload,
+    state: web::Data<Arc<RwLock<State>>>,
+    stdout_tx: web::Data<mpsc::Sender<Addr<IpcSession>>>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let session = IpcSession::new(state.get_ref().clone(), Role::Console);
+    let (addr, resp) = ws::start_with_addr(session, &req, stream)?;
+    let _ = stdout_tx.send(addr);
+    Ok(resp)
+}
+
+/// Starts the embedded webserver on its own thread and returns the port
+/// it's listening on along with the live main-session address. Binds to
+/// `Config::bind_host` (loopback by default), so exposing the dashboard to
+/// the team LAN is an explicit opt-in.
+pub fn launch_webserver(
+    state: Arc<RwLock<State>>,
+    cfg: Arc<RwLock<Config>>,
+    keybindings: SharedKeyBindings,
+    tx: mpsc::Sender<Addr<IpcSession>>,
+    stdout_tx: mpsc::Sender<Addr<IpcSession>>,
+) -> (u16, SharedMainAddr) {
+    let (port_tx, port_rx) = mpsc::channel();
+    let main_addr: SharedMainAddr = Arc::new(RwLock::new(None));
+    cfg.write().unwrap().enforce_auth_invariant();
+    let bind_host = cfg.read().unwrap().bind_host.clone();
+
+    {
+        let main_addr = main_addr.clone();
+
+        thread::spawn(move || {
+            let sys = actix_web::rt::System::new();
+
+            sys.block_on(async move {
+                let server = HttpServer::new(move || {
+                    App::new()
+                        // The embedded webview always connects via
+                        // http://localhost:{port}, so loopback peers are
+                        // exempted inside the middleware itself; every route
+                        // (including `/`, `/ws`, and the console stream) is
+                        // equally exposed to the LAN once bind_host isn't
+                        // loopback, so all of them need the same gate.
+                        .wrap(BearerAuth::new(cfg.clone()))
+                        .app_data(web::Data::new(state.clone()))
+                        .app_data(web::Data::new(cfg.clone()))
+                        .app_data(web::Data::new(keybindings.clone()))
+                        .app_data(web::Data::new(tx.clone()))
+                        .app_data(web::Data::new(stdout_tx.clone()))
+                        .app_data(web::Data::new(main_addr.clone()))
+                        .route("/", web::get().to(index))
+                        .route("/stdout", web::get().to(stdout))
+                        .route("/ws", web::get().to(ws_main))
+                        .route("/stdout/ws", web::get().to(ws_stdout))
+                        .route("/api/telemetry", web::get().to(telemetry))
+                        .route("/api/profile/import", web::post().to(import_profile))
+                        .route("/api/profile/export", web::get().to(export_profile))
+                        .route("/{path}", web::get().to(assets))
+                })
+                .bind((bind_host.as_str(), 0))
+                .unwrap();
+
+                let port = server.addrs().first().unwrap().port();
+                port_tx.send(port).unwrap();
+
+                server.run().await
+            })
+            .unwrap();
+        });
+    }
+
+    let port = port_rx.recv().unwrap();
+    (port, main_addr)
+}